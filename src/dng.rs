@@ -0,0 +1,290 @@
+//! Minimal baseline-TIFF/DNG writer.
+//!
+//! [`cameraunit::DynamicSerialImage::save`] only writes PNG, which discards
+//! everything in [`ImageMetaData`] (exposure, temperature, binning, ROI
+//! origin). [`save_dng`] instead writes a single-strip, 16-bit baseline TIFF
+//! carrying the subset of DNG tags stacking pipelines expect, without
+//! pulling in a TIFF/DNG crate.
+
+use std::{
+    fs::File,
+    io::{self, Write},
+    path::Path,
+};
+
+use cameraunit::{Error, ImageMetaData};
+
+/// `PhotometricInterpretation` for a raw grayscale (debayered or mono) strip.
+const PHOTOMETRIC_BLACK_IS_ZERO: u16 = 1;
+/// `PhotometricInterpretation` for an un-debayered Bayer (CFA) strip.
+const PHOTOMETRIC_CFA: u16 = 32803;
+
+const TAG_NEW_SUBFILE_TYPE: u16 = 254;
+const TAG_IMAGE_WIDTH: u16 = 256;
+const TAG_IMAGE_LENGTH: u16 = 257;
+const TAG_BITS_PER_SAMPLE: u16 = 258;
+const TAG_COMPRESSION: u16 = 259;
+const TAG_PHOTOMETRIC_INTERPRETATION: u16 = 262;
+const TAG_STRIP_OFFSETS: u16 = 273;
+const TAG_SAMPLES_PER_PIXEL: u16 = 277;
+const TAG_ROWS_PER_STRIP: u16 = 278;
+const TAG_STRIP_BYTE_COUNTS: u16 = 279;
+const TAG_EXPOSURE_TIME: u16 = 33434;
+const TAG_TEMPERATURE_CELSIUS: u16 = 37500; // MakerNote
+const TAG_UNIQUE_CAMERA_MODEL: u16 = 50708;
+const TAG_BLACK_LEVEL: u16 = 50714;
+
+const TYPE_SHORT: u16 = 3;
+const TYPE_LONG: u16 = 4;
+const TYPE_ASCII: u16 = 2;
+const TYPE_RATIONAL: u16 = 5;
+const TYPE_SRATIONAL: u16 = 10;
+
+/// A single 12-byte TIFF IFD entry, plus any overflow bytes it points to.
+struct IfdEntry {
+    tag: u16,
+    field_type: u16,
+    count: u32,
+    /// Either the inline value (left-justified in 4 bytes) or, if `overflow`
+    /// is non-empty, a placeholder that gets patched with the real offset.
+    inline: [u8; 4],
+    overflow: Vec<u8>,
+}
+
+impl IfdEntry {
+    fn inline_u32(tag: u16, field_type: u16, value: u32) -> Self {
+        IfdEntry {
+            tag,
+            field_type,
+            count: 1,
+            inline: value.to_le_bytes(),
+            overflow: Vec::new(),
+        }
+    }
+
+    fn inline_u16(tag: u16, value: u16) -> Self {
+        let mut inline = [0u8; 4];
+        inline[0..2].copy_from_slice(&value.to_le_bytes());
+        IfdEntry {
+            tag,
+            field_type: TYPE_SHORT,
+            count: 1,
+            inline,
+            overflow: Vec::new(),
+        }
+    }
+
+    fn ascii(tag: u16, value: &str) -> Self {
+        let mut bytes = value.as_bytes().to_vec();
+        bytes.push(0);
+        IfdEntry {
+            tag,
+            field_type: TYPE_ASCII,
+            count: bytes.len() as u32,
+            inline: [0; 4],
+            overflow: bytes,
+        }
+    }
+
+    fn rational(tag: u16, numerator: u32, denominator: u32) -> Self {
+        let mut bytes = Vec::with_capacity(8);
+        bytes.extend_from_slice(&numerator.to_le_bytes());
+        bytes.extend_from_slice(&denominator.to_le_bytes());
+        IfdEntry {
+            tag,
+            field_type: TYPE_RATIONAL,
+            count: 1,
+            inline: [0; 4],
+            overflow: bytes,
+        }
+    }
+
+    fn srational(tag: u16, numerator: i32, denominator: i32) -> Self {
+        let mut bytes = Vec::with_capacity(8);
+        bytes.extend_from_slice(&numerator.to_le_bytes());
+        bytes.extend_from_slice(&denominator.to_le_bytes());
+        IfdEntry {
+            tag,
+            field_type: TYPE_SRATIONAL,
+            count: 1,
+            inline: [0; 4],
+            overflow: bytes,
+        }
+    }
+
+    fn needs_offset(&self) -> bool {
+        !self.overflow.is_empty()
+    }
+}
+
+/// Write `data` (a `width * height` row-major 16-bit grayscale buffer) to
+/// `path` as a baseline TIFF/DNG, tagging it with the exposure time, sensor
+/// temperature, and camera name from `meta`, which a PNG export would lose.
+/// Binning and ROI origin from `meta` are not written to the file.
+///
+/// `is_cfa` selects `PhotometricInterpretation`: `true` for an un-debayered
+/// Bayer mosaic, `false` for already-demosaiced or mono data.
+///
+/// # Errors
+///  - [`cameraunit::Error::InvalidValue`] - `data.len()` does not match
+///    `width * height`.
+///  - [`cameraunit::Error::GeneralError`] - The file could not be written.
+pub fn save_dng<P: AsRef<Path>>(
+    path: P,
+    width: u32,
+    height: u32,
+    data: &[u16],
+    meta: &ImageMetaData,
+    is_cfa: bool,
+) -> Result<(), Error> {
+    if data.len() != (width * height) as usize {
+        return Err(Error::InvalidValue(format!(
+            "Pixel buffer length {} does not match {}x{}",
+            data.len(),
+            width,
+            height
+        )));
+    }
+
+    let exposure_ms = meta.exposure.as_millis().max(1) as u32;
+    let model = meta.camera_name.clone();
+
+    let mut entries = vec![
+        IfdEntry::inline_u32(TAG_NEW_SUBFILE_TYPE, TYPE_LONG, 0),
+        IfdEntry::inline_u32(TAG_IMAGE_WIDTH, TYPE_LONG, width),
+        IfdEntry::inline_u32(TAG_IMAGE_LENGTH, TYPE_LONG, height),
+        IfdEntry::inline_u16(TAG_BITS_PER_SAMPLE, 16),
+        IfdEntry::inline_u16(TAG_COMPRESSION, 1),
+        IfdEntry::inline_u16(
+            TAG_PHOTOMETRIC_INTERPRETATION,
+            if is_cfa {
+                PHOTOMETRIC_CFA
+            } else {
+                PHOTOMETRIC_BLACK_IS_ZERO
+            },
+        ),
+        IfdEntry::inline_u32(TAG_STRIP_OFFSETS, TYPE_LONG, 0), // patched below
+        IfdEntry::inline_u16(TAG_SAMPLES_PER_PIXEL, 1),
+        IfdEntry::inline_u32(TAG_ROWS_PER_STRIP, TYPE_LONG, height),
+        IfdEntry::inline_u32(
+            TAG_STRIP_BYTE_COUNTS,
+            TYPE_LONG,
+            width * height * 2,
+        ),
+        IfdEntry::rational(TAG_EXPOSURE_TIME, exposure_ms, 1000),
+        IfdEntry::srational(TAG_TEMPERATURE_CELSIUS, (meta.temperature * 100.0) as i32, 100),
+        IfdEntry::inline_u16(TAG_BLACK_LEVEL, 0),
+        IfdEntry::ascii(TAG_UNIQUE_CAMERA_MODEL, &model),
+    ];
+    entries.sort_by_key(|e| e.tag);
+
+    // Header (8 bytes) + IFD entry count (2) + entries (12 each) + next-IFD
+    // offset (4) is where the overflow data for oversized fields starts.
+    let ifd_offset: u32 = 8;
+    let overflow_offset: u32 =
+        ifd_offset + 2 + entries.len() as u32 * 12 + 4;
+
+    let mut overflow_cursor = overflow_offset;
+    let mut offsets = Vec::with_capacity(entries.len());
+    for entry in &entries {
+        offsets.push(overflow_cursor);
+        if entry.needs_offset() {
+            overflow_cursor += entry.overflow.len() as u32;
+            // TIFF pads every value to an even byte boundary.
+            if entry.overflow.len() % 2 == 1 {
+                overflow_cursor += 1;
+            }
+        }
+    }
+    let strip_offset = overflow_cursor;
+
+    let mut buf = Vec::with_capacity(strip_offset as usize + data.len() * 2);
+    buf.extend_from_slice(b"II"); // little-endian byte order
+    buf.extend_from_slice(&42u16.to_le_bytes()); // TIFF magic
+    buf.extend_from_slice(&ifd_offset.to_le_bytes());
+
+    buf.extend_from_slice(&(entries.len() as u16).to_le_bytes());
+    for (entry, offset) in entries.iter().zip(&offsets) {
+        buf.extend_from_slice(&entry.tag.to_le_bytes());
+        buf.extend_from_slice(&entry.field_type.to_le_bytes());
+        buf.extend_from_slice(&entry.count.to_le_bytes());
+        if entry.tag == TAG_STRIP_OFFSETS {
+            buf.extend_from_slice(&strip_offset.to_le_bytes());
+        } else if entry.needs_offset() {
+            buf.extend_from_slice(&offset.to_le_bytes());
+        } else {
+            buf.extend_from_slice(&entry.inline);
+        }
+    }
+    buf.extend_from_slice(&0u32.to_le_bytes()); // no next IFD
+
+    for entry in &entries {
+        if entry.needs_offset() {
+            buf.extend_from_slice(&entry.overflow);
+            if entry.overflow.len() % 2 == 1 {
+                buf.push(0);
+            }
+        }
+    }
+
+    for pixel in data {
+        buf.extend_from_slice(&pixel.to_le_bytes());
+    }
+
+    write_all(path, &buf)
+}
+
+fn write_all<P: AsRef<Path>>(path: P, buf: &[u8]) -> Result<(), Error> {
+    let mut file = File::create(path)
+        .map_err(|e| Error::GeneralError(format!("Error creating DNG file: {}", e)))?;
+    file.write_all(buf)
+        .map_err(|e: io::Error| Error::GeneralError(format!("Error writing DNG file: {}", e)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    #[test]
+    fn rejects_mismatched_buffer_length() {
+        let meta = ImageMetaData::default();
+        let data = vec![0u16; 4];
+        let err = save_dng(
+            std::env::temp_dir().join("dng_test_mismatch.tif"),
+            3,
+            3,
+            &data,
+            &meta,
+            false,
+        );
+        assert!(matches!(err, Err(Error::InvalidValue(_))));
+    }
+
+    #[test]
+    fn writes_a_readable_tiff_header_and_pixels() {
+        let mut meta = ImageMetaData::default();
+        meta.exposure = Duration::from_millis(250);
+        meta.temperature = -10.5;
+        meta.camera_name = "Test Camera".to_string();
+
+        let width = 2;
+        let height = 2;
+        let data = vec![1u16, 2, 3, 4];
+        let path = std::env::temp_dir().join("dng_test_roundtrip.tif");
+        save_dng(&path, width, height, &data, &meta, false).unwrap();
+
+        let bytes = std::fs::read(&path).unwrap();
+        std::fs::remove_file(&path).ok();
+
+        assert_eq!(&bytes[0..2], b"II");
+        assert_eq!(u16::from_le_bytes([bytes[2], bytes[3]]), 42);
+
+        let pixel_bytes = &bytes[bytes.len() - data.len() * 2..];
+        let pixels: Vec<u16> = pixel_bytes
+            .chunks_exact(2)
+            .map(|c| u16::from_le_bytes([c[0], c[1]]))
+            .collect();
+        assert_eq!(pixels, data);
+    }
+}
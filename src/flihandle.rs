@@ -1,13 +1,18 @@
 #![allow(unused)]
 use std::{
-    ffi::{c_long, CStr},
-    sync::atomic::{AtomicBool, AtomicU32, AtomicU64, Ordering},
-    time::Duration,
+    ffi::{c_long, c_void, CStr},
+    sync::{
+        atomic::{AtomicBool, AtomicU32, AtomicU64, Ordering},
+        Arc,
+    },
+    thread::{self, JoinHandle},
+    time::{Duration, Instant},
 };
 
 use crate::fli_ffi::*;
 use cameraunit::{Error, PixelBpp, ROI};
 
+use crossbeam_channel::{bounded, Receiver, Sender};
 use image::Pixel;
 use log::warn;
 
@@ -22,7 +27,119 @@ macro_rules! FLICALL {
     };
 }
 
-pub const FLIDOMAIN_CAMERA: i64 = (FLIDEVICE_CAMERA | FLIDOMAIN_USB) as i64;
+/// Upper bound the configured pre-exposure flush count is clamped to.
+const MAX_NUM_FLUSHES: u32 = 16;
+
+/// The bus an FLI camera is attached over, used both to scan for devices and
+/// to reopen one returned by discovery.
+///
+/// `get_camera_ids` scans [`CameraTransport::ALL`], so the parallel-port
+/// family `libfli-camera-parport` supports (and the serial domain some older
+/// cameras use) is found alongside USB, and tags each returned ID with its
+/// transport so `open_camera` can reopen it on the right bus.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CameraTransport {
+    /// USB-attached FLI camera.
+    Usb,
+    /// Legacy parallel-port FLI camera.
+    ParallelPort,
+    /// Serial-attached FLI camera.
+    Serial,
+}
+
+impl CameraTransport {
+    /// Every transport `get_camera_ids` scans, in the order results are
+    /// merged.
+    pub const ALL: [CameraTransport; 3] = [
+        CameraTransport::Usb,
+        CameraTransport::ParallelPort,
+        CameraTransport::Serial,
+    ];
+
+    /// The combined `FLIDEVICE_CAMERA | FLIDOMAIN_*` value `FLIList`/`FLIOpen`
+    /// expect.
+    pub fn domain(self) -> i64 {
+        let domain = match self {
+            CameraTransport::Usb => FLIDOMAIN_USB,
+            CameraTransport::ParallelPort => FLIDOMAIN_PARALLEL_PORT,
+            CameraTransport::Serial => FLIDOMAIN_SERIAL,
+        };
+        (FLIDEVICE_CAMERA | domain) as i64
+    }
+
+    /// The prefix `get_camera_ids` tags IDs from this transport with.
+    pub fn prefix(self) -> &'static str {
+        match self {
+            CameraTransport::Usb => "usb",
+            CameraTransport::ParallelPort => "parport",
+            CameraTransport::Serial => "serial",
+        }
+    }
+
+    /// Parse a prefix produced by [`CameraTransport::prefix`].
+    pub fn parse_prefix(prefix: &str) -> Option<Self> {
+        match prefix {
+            "usb" => Some(CameraTransport::Usb),
+            "parport" => Some(CameraTransport::ParallelPort),
+            "serial" => Some(CameraTransport::Serial),
+            _ => None,
+        }
+    }
+}
+
+/// The device-level state reported by `FLIGetDeviceStatus`, combined with
+/// whether a completed frame is waiting to be grabbed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ExposureState {
+    /// No exposure is in progress.
+    Idle,
+    /// Armed and waiting for an external trigger.
+    WaitingForTrigger,
+    /// Integrating, with the given number of milliseconds left.
+    Exposing(u64),
+    /// Integration finished; the sensor is being read out.
+    ReadingOut,
+    /// Readout finished; a frame is waiting for `FLIGrabFrame`.
+    ReadyToDownload,
+}
+
+/// Exposure frame classification, controlling shutter behavior during
+/// integration. Mirrors the classic `Expose(duration, light)` distinction
+/// used by INDI camera drivers, so a calibration pipeline can request
+/// dark/bias frames through the same capture path as science frames.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FrameType {
+    /// Shutter opens normally for a light frame.
+    Normal,
+    /// Shutter stays closed for the full integration.
+    Dark,
+    /// Shutter stays closed and the minimum exposure time is forced.
+    Bias,
+    /// Shutter opens against a uniform illumination source.
+    Flat,
+}
+
+impl From<u32> for FrameType {
+    fn from(value: u32) -> Self {
+        match value {
+            1 => FrameType::Dark,
+            2 => FrameType::Bias,
+            3 => FrameType::Flat,
+            _ => FrameType::Normal,
+        }
+    }
+}
+
+impl From<FrameType> for u32 {
+    fn from(frame_type: FrameType) -> Self {
+        match frame_type {
+            FrameType::Normal => 0,
+            FrameType::Dark => 1,
+            FrameType::Bias => 2,
+            FrameType::Flat => 3,
+        }
+    }
+}
 
 #[derive(Debug)]
 pub struct FLIHandle {
@@ -38,6 +155,15 @@ pub struct FLIHandle {
     pub dark: AtomicBool,
     /// The pixel bit depth.
     pub bpp: AtomicU32,
+    /// The last temperature setpoint requested via [`FLIHandle::set_temperature`],
+    /// stored as `f32` bits since the FLI SDK has no getter for it.
+    pub setpoint: AtomicU32,
+    /// The current frame classification, stored as `u32` via [`FrameType`]'s
+    /// `From` impls.
+    pub frame_type: AtomicU32,
+    /// Pre-exposure flush count passed to `FLISetNFlushes`; zero disables
+    /// flushing.
+    pub num_flushes: AtomicU32,
 }
 
 
@@ -55,6 +181,18 @@ impl Drop for FLIHandle {
     }
 }
 
+/// A snapshot of cooler telemetry, combining setpoint, current sensor
+/// temperature, and cooler PWM power in a single query.
+#[derive(Debug, Clone, Copy)]
+pub struct CoolerStatus {
+    /// Last temperature setpoint requested.
+    pub setpoint: f32,
+    /// Current sensor temperature, in Celsius.
+    pub temperature: f32,
+    /// Cooler drive power, in percent.
+    pub power: f64,
+}
+
 impl FLIHandle {
     pub fn new(handle: flidev_t) -> Self {
         FLIHandle {
@@ -64,9 +202,18 @@ impl FLIHandle {
             ready: AtomicBool::new(false),
             dark: AtomicBool::new(false),
             bpp: AtomicU32::new(16),
+            setpoint: AtomicU32::new(25.0f32.to_bits()),
+            frame_type: AtomicU32::new(FrameType::Normal.into()),
+            num_flushes: AtomicU32::new(0),
         }
     }
 
+    /// The frame classification set by the last
+    /// [`crate::CameraUnitFLI::set_frame_type`] call.
+    pub fn get_frame_type(&self) -> FrameType {
+        self.frame_type.load(Ordering::SeqCst).into()
+    }
+
     pub fn image_ready(&self) -> Result<bool, Error> {
         let capturing = self.capturing.load(Ordering::SeqCst);
         if capturing {
@@ -113,6 +260,81 @@ impl FLIHandle {
         }
     }
 
+    /// Time remaining in the current exposure, as reported by
+    /// `FLIGetExposureStatus`. Zero once integration has finished, even if
+    /// readout is still in progress.
+    pub fn get_exposure_time_left(&self) -> Result<Duration, Error> {
+        let mut time_left: c_long = 0;
+        FLICALL!(FLIGetExposureStatus(self.dev, &mut time_left));
+        Ok(Duration::from_millis(time_left.max(0) as u64))
+    }
+
+    /// A structured snapshot of where the camera is in the expose/readout
+    /// cycle, combining `FLIGetDeviceStatus` and `FLIGetExposureStatus`.
+    pub fn device_status(&self) -> Result<ExposureState, Error> {
+        if !self.capturing.load(Ordering::SeqCst) {
+            return Ok(ExposureState::Idle);
+        }
+        if self.ready.load(Ordering::SeqCst) {
+            return Ok(ExposureState::ReadyToDownload);
+        }
+
+        let time_left = self.get_exposure_time_left()?;
+        if time_left > Duration::ZERO {
+            return Ok(ExposureState::Exposing(time_left.as_millis() as u64));
+        }
+
+        let mut status: c_long = 0;
+        FLICALL!(FLIGetDeviceStatus(self.dev, &mut status));
+        let status = status as u32;
+
+        // FLI_CAMERA_STATUS_READING_CCD is a separate bit outside
+        // FLI_CAMERA_STATUS_MASK, not one of the masked state values, so it
+        // has to be checked on its own or readout is indistinguishable from
+        // idle below.
+        if status & FLI_CAMERA_STATUS_READING_CCD != 0 {
+            return Ok(ExposureState::ReadingOut);
+        }
+
+        match status & FLI_CAMERA_STATUS_MASK {
+            FLI_CAMERA_STATUS_IDLE => Ok(ExposureState::Idle),
+            FLI_CAMERA_STATUS_WAITING_FOR_TRIGGER => Ok(ExposureState::WaitingForTrigger),
+            FLI_CAMERA_STATUS_EXPOSING => Ok(ExposureState::Exposing(0)),
+            _ => Ok(ExposureState::ReadingOut),
+        }
+    }
+
+    /// Set the flush count passed to `FLISetNFlushes`, clamped to
+    /// [`MAX_NUM_FLUSHES`]. While it is non-zero, `flush()` clears the active
+    /// readout height before each exposure. CCDs accumulate dark current
+    /// between exposures; flushing clears it so it doesn't ghost into the
+    /// next frame.
+    pub fn set_num_flushes(&self, n: u32) -> Result<(), Error> {
+        let n = n.min(MAX_NUM_FLUSHES);
+        FLICALL!(FLISetNFlushes(self.dev, n as c_long));
+        self.num_flushes.store(n, Ordering::SeqCst);
+        Ok(())
+    }
+
+    /// The pre-exposure flush count configured by [`FLIHandle::set_num_flushes`].
+    pub fn get_num_flushes(&self) -> u32 {
+        self.num_flushes.load(Ordering::SeqCst)
+    }
+
+    /// Flush the active readout area to clear accumulated charge.
+    ///
+    /// Called automatically before each exposure when
+    /// [`FLIHandle::get_num_flushes`] is non-zero; can also be invoked
+    /// explicitly between exposures.
+    pub fn flush(&self) -> Result<(), Error> {
+        if self.capturing.load(Ordering::SeqCst) {
+            return Err(Error::ExposureInProgress);
+        }
+        let height = self.get_readout_dim()?.height;
+        FLICALL!(FLIFlushRow(self.dev, height as c_long, 1));
+        Ok(())
+    }
+
     pub fn cancel_capture(&self) -> Result<(), Error> {
         FLICALL!(FLICancelExposure(self.dev));
         self.capturing.store(false, Ordering::SeqCst);
@@ -134,15 +356,61 @@ impl FLIHandle {
             )));
         }
         FLICALL!(FLISetTemperature(self.dev, temp as f64));
+        self.setpoint.store(temp.to_bits(), Ordering::SeqCst);
         Ok(())
     }
 
+    /// The last temperature setpoint requested, since the FLI SDK has no
+    /// query for it.
+    pub fn get_setpoint(&self) -> f32 {
+        f32::from_bits(self.setpoint.load(Ordering::SeqCst))
+    }
+
     pub fn get_cooler_power(&self) -> Result<f64, Error> {
         let mut power: f64 = 0.;
         FLICALL!(FLIGetCoolerPower(self.dev, &mut power));
         Ok(power)
     }
 
+    /// Combine setpoint, current temperature, and cooler power into one
+    /// telemetry snapshot.
+    pub fn cooler_status(&self) -> Result<CoolerStatus, Error> {
+        Ok(CoolerStatus {
+            setpoint: self.get_setpoint(),
+            temperature: self.get_temperature()?,
+            power: self.get_cooler_power()?,
+        })
+    }
+
+    /// Poll [`FLIHandle::get_temperature`] until the sensor is within
+    /// `tolerance` of `target`, or return an error once `timeout` elapses.
+    ///
+    /// Lets callers confirm the detector has thermally settled before
+    /// starting a sequence instead of polling `get_temperature`/
+    /// `get_cooler_power` manually.
+    pub fn wait_for_temperature(
+        &self,
+        target: f32,
+        tolerance: f32,
+        timeout: Duration,
+    ) -> Result<f32, Error> {
+        let start = Instant::now();
+        loop {
+            let temp = self.get_temperature()?;
+            if (temp - target).abs() <= tolerance {
+                return Ok(temp);
+            }
+            let elapsed = start.elapsed();
+            if elapsed >= timeout {
+                return Err(Error::GeneralError(format!(
+                    "Timed out waiting for temperature to settle at {:.1}+/-{:.1}C (last read {:.1}C)",
+                    target, tolerance, temp
+                )));
+            }
+            thread::sleep((timeout - elapsed).min(Duration::from_millis(500)));
+        }
+    }
+
     pub fn get_model(&self) -> Result<String, Error> {
         let mut model = [0i8; 128];
         FLICALL!(FLIGetModel(self.dev, model.as_mut_ptr(), model.len()));
@@ -289,3 +557,176 @@ impl FLIHandle {
         self.bpp.load(Ordering::SeqCst).into()
     }
 }
+
+/// A raw grabbed frame handed out by [`FLIStream`], paired with the
+/// dimensions it was read out at.
+pub struct RawBuffer {
+    /// The grabbed pixels, row-major.
+    pub data: Vec<u16>,
+    /// Width in pixels at the time of readout.
+    pub width: u32,
+    /// Height in pixels at the time of readout.
+    pub height: u32,
+}
+
+/// A dedicated background thread that repeatedly arms an exposure on a
+/// [`FLIHandle`], polls until it completes, and grabs the frame.
+///
+/// This is the low-level primitive [`crate::CameraUnitFLI::start_stream`]
+/// builds on internally. Neither `FLIHandle` nor `FLIStream` is exposed
+/// outside the crate, so external callers always go through `start_stream`,
+/// which layers a command channel on top for mid-stream exposure/ROI
+/// changes that `FLIStream` alone has no way to accept. Drained buffers sent
+/// back on [`FLIStream::free_buffers`] are reused for the next readout
+/// instead of being reallocated.
+pub struct FLIStream {
+    /// Receives each completed raw frame as it's grabbed.
+    pub frames: Receiver<RawBuffer>,
+    /// Accepts drained pixel buffers back from the consumer for recycling.
+    pub free_buffers: Sender<Vec<u16>>,
+    stop: Arc<AtomicBool>,
+    join: Option<JoinHandle<()>>,
+}
+
+impl FLIStream {
+    /// Spawn the worker thread. `handle` must not already be capturing.
+    pub fn start(handle: Arc<FLIHandle>) -> Self {
+        let stop = Arc::new(AtomicBool::new(false));
+        let worker_stop = stop.clone();
+        let (frame_tx, frame_rx) = bounded::<RawBuffer>(4);
+        let (free_tx, free_rx) = bounded::<Vec<u16>>(4);
+
+        let join = thread::spawn(move || {
+            while !worker_stop.load(Ordering::SeqCst) {
+                let roi = match handle.get_readout_dim() {
+                    Ok(roi) => roi,
+                    Err(e) => {
+                        warn!("FLIStream: error reading ROI: {}", e);
+                        break;
+                    }
+                };
+                let width = roi.width;
+                let height = roi.height;
+
+                if handle.get_num_flushes() > 0 {
+                    if let Err(e) = handle.flush() {
+                        warn!("FLIStream: error flushing before exposure: {}", e);
+                    }
+                }
+                handle.capturing.store(true, Ordering::SeqCst);
+                handle.ready.store(false, Ordering::SeqCst);
+                if unsafe { FLIExposeFrame(handle.dev) } != 0 {
+                    warn!("FLIStream: failed to start exposure");
+                    handle.capturing.store(false, Ordering::SeqCst);
+                    break;
+                }
+
+                loop {
+                    if worker_stop.load(Ordering::SeqCst) {
+                        let _ = handle.cancel_capture();
+                        return;
+                    }
+                    match handle.image_ready() {
+                        Ok(true) => break,
+                        Ok(false) => thread::sleep(Duration::from_millis(10)),
+                        Err(e) => {
+                            warn!("FLIStream: error polling exposure status: {}", e);
+                            handle.capturing.store(false, Ordering::SeqCst);
+                            return;
+                        }
+                    }
+                }
+
+                let mut buf = free_rx
+                    .try_recv()
+                    .unwrap_or_else(|_| vec![0u16; (width * height) as usize]);
+                buf.resize((width * height) as usize, 0);
+
+                let mut grabbed = 0;
+                let res = unsafe {
+                    FLIGrabFrame(
+                        handle.dev,
+                        buf.as_mut_ptr() as *mut c_void,
+                        (width * height * 2) as usize,
+                        &mut grabbed,
+                    )
+                };
+                handle.capturing.store(false, Ordering::SeqCst);
+                handle.ready.store(true, Ordering::SeqCst);
+                if res != 0 {
+                    warn!("FLIStream: error grabbing frame: {}", res);
+                    continue;
+                }
+
+                if frame_tx
+                    .send(RawBuffer {
+                        data: buf,
+                        width,
+                        height,
+                    })
+                    .is_err()
+                {
+                    break;
+                }
+            }
+        });
+
+        FLIStream {
+            frames: frame_rx,
+            free_buffers: free_tx,
+            stop,
+            join: Some(join),
+        }
+    }
+
+    /// Signal the worker to stop, cancel any in-flight exposure, and join
+    /// the thread.
+    pub fn stop_stream(&mut self) {
+        self.stop.store(true, Ordering::SeqCst);
+        if let Some(join) = self.join.take() {
+            let _ = join.join();
+        }
+    }
+}
+
+impl Drop for FLIStream {
+    fn drop(&mut self) {
+        self.stop_stream();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn camera_transport_prefix_round_trips() {
+        for transport in CameraTransport::ALL {
+            let prefix = transport.prefix();
+            assert_eq!(CameraTransport::parse_prefix(prefix), Some(transport));
+        }
+    }
+
+    #[test]
+    fn camera_transport_parse_prefix_rejects_unknown() {
+        assert_eq!(CameraTransport::parse_prefix("bluetooth"), None);
+        assert_eq!(CameraTransport::parse_prefix(""), None);
+    }
+
+    #[test]
+    fn frame_type_round_trips_through_u32() {
+        for frame_type in [
+            FrameType::Normal,
+            FrameType::Dark,
+            FrameType::Bias,
+            FrameType::Flat,
+        ] {
+            assert_eq!(FrameType::from(u32::from(frame_type)), frame_type);
+        }
+    }
+
+    #[test]
+    fn frame_type_from_unknown_u32_defaults_to_normal() {
+        assert_eq!(FrameType::from(42), FrameType::Normal);
+    }
+}
@@ -3,7 +3,10 @@ mod fli_ffi;
 #[macro_use]
 mod flihandle;
 
+mod dng;
 mod flicamera;
+mod preview;
+mod settings;
 
 // pub use flicamera::{
 //     get_camera_ids, num_cameras, open_camera, open_first_camera, ASICameraProps, ASIImageFormat,
@@ -18,7 +21,12 @@ pub use cameraunit::{
 
 pub use flicamera::{
     get_camera_ids, num_cameras, open_camera, open_first_camera, CameraInfoFLI, CameraUnitFLI,
+    StreamCommand,
 };
+pub use flihandle::{CameraTransport, CoolerStatus, ExposureState, FrameType};
+
+pub use dng::save_dng;
+pub use settings::CameraSettingsFLI;
 
 #[cfg(test)]
 mod tests {
@@ -0,0 +1,77 @@
+//! Box-averaged downscaling for live-view preview frames.
+//!
+//! Full 16-bit frames are too large to push to a display at frame rate, so
+//! [`downscale_u16`] reduces a captured buffer in place of re-downloading a
+//! smaller one, by averaging each `scale x scale` block into a single pixel.
+
+/// Box-average `data` (a `width * height` row-major 16-bit buffer) down by
+/// `scale`, producing a buffer of `width/scale x height/scale`, each rounded
+/// up.
+///
+/// Block sums are accumulated in `u32`, which holds a full `scale * scale`
+/// block of saturated 16-bit pixels for any `scale` up to 256. Edge blocks that fall outside
+/// `width`/`height` (when a dimension isn't evenly divisible by `scale`) are
+/// averaged over only the pixels actually present.
+///
+/// Returns the downscaled buffer along with its width and height. `scale`
+/// must be at least 1; a `scale` of 1 returns `data` unchanged.
+pub fn downscale_u16(data: &[u16], width: u32, height: u32, scale: u32) -> (Vec<u16>, u32, u32) {
+    if scale <= 1 {
+        return (data.to_vec(), width, height);
+    }
+
+    let out_width = width.div_ceil(scale).max(1);
+    let out_height = height.div_ceil(scale).max(1);
+    let mut out = vec![0u16; (out_width * out_height) as usize];
+
+    for oy in 0..out_height {
+        let y0 = oy * scale;
+        let y1 = (y0 + scale).min(height);
+        for ox in 0..out_width {
+            let x0 = ox * scale;
+            let x1 = (x0 + scale).min(width);
+
+            let mut sum: u32 = 0;
+            let mut count: u32 = 0;
+            for y in y0..y1 {
+                let row = (y * width) as usize;
+                for x in x0..x1 {
+                    sum += data[row + x as usize] as u32;
+                    count += 1;
+                }
+            }
+            out[(oy * out_width + ox) as usize] = if count > 0 {
+                (sum / count) as u16
+            } else {
+                0
+            };
+        }
+    }
+
+    (out, out_width, out_height)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn scale_of_one_is_a_passthrough() {
+        let data = vec![1u16, 2, 3, 4];
+        assert_eq!(downscale_u16(&data, 2, 2, 1), (data, 2, 2));
+    }
+
+    #[test]
+    fn edge_blocks_average_only_the_pixels_present() {
+        #[rustfmt::skip]
+        let data: Vec<u16> = vec![
+            0, 1, 2, 3,
+            4, 5, 6, 7,
+            8, 9, 10, 11,
+            12, 13, 14, 15,
+        ];
+        let (out, out_width, out_height) = downscale_u16(&data, 4, 4, 3);
+        assert_eq!((out_width, out_height), (2, 2));
+        assert_eq!(out, vec![5, 7, 13, 15]);
+    }
+}
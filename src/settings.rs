@@ -0,0 +1,80 @@
+//! A cached snapshot of every parameter [`crate::CameraUnitFLI`] can control,
+//! so callers can save/restore a configuration instead of issuing a dozen
+//! individual FFI round-trips. The struct derives `Clone` and `Debug` only;
+//! it has no serialization support of its own.
+
+use std::time::Duration;
+
+use cameraunit::{PixelBpp, ROI};
+
+use crate::flihandle::FrameType;
+
+/// A full configuration snapshot for a [`crate::CameraUnitFLI`], returned by
+/// `get_settings()` and pushed back by `apply_settings()`.
+#[derive(Debug, Clone)]
+pub struct CameraSettingsFLI {
+    /// Exposure time.
+    pub exposure: Duration,
+    /// Region of interest, including binning.
+    pub roi: ROI,
+    /// Pixel bit depth.
+    pub bpp: PixelBpp,
+    /// Frame classification (light/dark/bias/flat), controlling shutter
+    /// behavior during exposure.
+    pub frame_type: FrameType,
+    /// Pre-exposure flush count; zero disables flushing.
+    pub num_flushes: u32,
+    /// Last requested cooler setpoint, in Celsius.
+    pub target_temperature: f32,
+    /// CCD width in pixels, read-only.
+    pub array_width: u32,
+    /// CCD height in pixels, read-only.
+    pub array_height: u32,
+    /// Pixel size in microns (x, y), read-only.
+    pub pixel_size: (f64, f64),
+    /// Camera serial number, read-only.
+    pub serial: String,
+    /// Camera model string, read-only.
+    pub model: String,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample() -> CameraSettingsFLI {
+        CameraSettingsFLI {
+            exposure: Duration::from_millis(250),
+            roi: ROI {
+                x_min: 0,
+                y_min: 0,
+                width: 512,
+                height: 512,
+                bin_x: 1,
+                bin_y: 1,
+            },
+            bpp: PixelBpp::Bpp16,
+            frame_type: FrameType::Dark,
+            num_flushes: 2,
+            target_temperature: -10.0,
+            array_width: 1024,
+            array_height: 1024,
+            pixel_size: (3.8, 3.8),
+            serial: "ML1234".to_string(),
+            model: "MicroLine ML50100".to_string(),
+        }
+    }
+
+    #[test]
+    fn clone_preserves_fields() {
+        let settings = sample();
+        let cloned = settings.clone();
+        assert_eq!(cloned.exposure, settings.exposure);
+        assert_eq!(cloned.bpp, settings.bpp);
+        assert_eq!(cloned.frame_type, settings.frame_type);
+        assert_eq!(cloned.num_flushes, settings.num_flushes);
+        assert_eq!(cloned.target_temperature, settings.target_temperature);
+        assert_eq!(cloned.serial, settings.serial);
+        assert_eq!(cloned.model, settings.model);
+    }
+}